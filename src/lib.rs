@@ -1,11 +1,164 @@
 use async_trait::async_trait;
+use std::convert::Infallible;
 use std::future::Future;
+#[cfg(not(feature = "async-trait"))]
+use std::pin::Pin;
 
+/// Boxed, object-safe `AsyncFn`. Every [`AsyncFn::call`] heap-allocates its future via
+/// `async-trait`; keep this around for callers that need to store handlers as trait
+/// objects. The default, allocation-free trait lives below, gated out when this feature
+/// is enabled.
+#[cfg(feature = "async-trait")]
 #[async_trait]
 pub trait AsyncFn<Args, Output> {
     async fn call(&self, args: Args) -> Output;
+
+    /// Builds `Args` by running [`FromContext::from_context`] for each element against
+    /// `ctx`, short-circuiting on the first error, then invokes [`AsyncFn::call`] with
+    /// the assembled tuple.
+    async fn call_extract<Ctx>(&self, ctx: &Ctx) -> Result<Output, Args::Error>
+    where
+        Ctx: Sync + 'async_trait,
+        Args: FromContext<Ctx> + Send + 'async_trait,
+        Self: Sync + Sized,
+    {
+        let args = Args::from_context(ctx).await?;
+        Ok(self.call(args).await)
+    }
+
+    /// Awaits [`AsyncFn::call`] and normalizes its `Output` into `R` via
+    /// [`IntoResponse`], so handlers with different concrete outputs can share one
+    /// dispatch surface.
+    async fn call_into<R>(&self, args: Args) -> R
+    where
+        Args: Send + 'async_trait,
+        Output: IntoResponse<R> + 'async_trait,
+        R: 'async_trait,
+        Self: Sync + Sized,
+    {
+        self.call(args).await.into_response()
+    }
 }
 
+/// Allocation-free `AsyncFn`: `call` returns the handler's own future type directly
+/// instead of a boxed trait object, so dispatch costs nothing beyond the `Fn` call
+/// itself. `call_extract`/`call_into` aren't on that hot path, so they box their
+/// returned future for convenience rather than threading an associated type through
+/// every combination of extractor and responder.
+#[cfg(not(feature = "async-trait"))]
+pub trait AsyncFn<Args, Output> {
+    /// The future returned by [`AsyncFn::call`], set directly to the handler's `async
+    /// fn` future by the `ary!` macro.
+    type Fut: Future<Output = Output> + Send;
+
+    fn call(&self, args: Args) -> Self::Fut;
+
+    /// Builds `Args` by running [`FromContext::from_context`] for each element against
+    /// `ctx`, short-circuiting on the first error, then invokes [`AsyncFn::call`] with
+    /// the assembled tuple.
+    fn call_extract<'a, Ctx>(
+        &'a self,
+        ctx: &'a Ctx,
+    ) -> Pin<Box<dyn Future<Output = Result<Output, Args::Error>> + Send + 'a>>
+    where
+        Ctx: Sync,
+        Args: FromContext<Ctx> + Send + 'a,
+        Self: Sync,
+        Self::Fut: 'a,
+    {
+        Box::pin(async move {
+            let args = Args::from_context(ctx).await?;
+            Ok(self.call(args).await)
+        })
+    }
+
+    /// Awaits [`AsyncFn::call`] and normalizes its `Output` into `R` via
+    /// [`IntoResponse`], so handlers with different concrete outputs can share one
+    /// dispatch surface.
+    fn call_into<'a, R>(&'a self, args: Args) -> Pin<Box<dyn Future<Output = R> + Send + 'a>>
+    where
+        Args: Send + 'a,
+        Output: IntoResponse<R> + 'a,
+        R: 'a,
+        Self: Sync,
+        Self::Fut: 'a,
+    {
+        Box::pin(async move { self.call(args).await.into_response() })
+    }
+}
+
+/// Converts a handler's concrete `Output` into a single normalized response type `R`.
+/// Mirrors actix-web's `Responder`, expressed as a plain conversion so any
+/// `Output: Into<R>` is usable without writing a dedicated impl.
+pub trait IntoResponse<R> {
+    fn into_response(self) -> R;
+}
+
+impl<T, R> IntoResponse<R> for T
+where
+    T: Into<R>,
+{
+    fn into_response(self) -> R {
+        self.into()
+    }
+}
+
+/// Extracts `Self` out of a shared context `Ctx`, failing with `Self::Error` if the
+/// value can't be produced. Borrowed from actix-web's `FromRequest`, but generic over
+/// any context type rather than being tied to a single request type.
+#[async_trait]
+pub trait FromContext<Ctx>: Sized {
+    type Error;
+
+    async fn from_context(ctx: &Ctx) -> Result<Self, Self::Error>;
+}
+
+#[async_trait]
+impl<Ctx: Sync> FromContext<Ctx> for () {
+    type Error = Infallible;
+
+    async fn from_context(_ctx: &Ctx) -> Result<Self, Self::Error> {
+        Ok(())
+    }
+}
+
+/// Generates a [`FromContext`] impl for an N-ary tuple, extracting each element from
+/// the shared context independently and short-circuiting on the first error. Each
+/// element is allowed its own `Error` type as long as it converts into the tuple's
+/// `Err` via `From`, so e.g. an auth extractor and a body extractor with unrelated
+/// error types can still be combined into one tuple.
+macro_rules! ary_context ({ $($param:ident)* } => {
+    #[async_trait::async_trait]
+    impl<Ctx, Err, $($param,)*> FromContext<Ctx> for ($($param,)*)
+    where
+        Ctx: Sync,
+        $($param: FromContext<Ctx> + Send,)*
+        $(Err: From<$param::Error>,)*
+    {
+        type Error = Err;
+
+        #[inline]
+        #[allow(non_snake_case)]
+        async fn from_context(ctx: &Ctx) -> Result<Self, Self::Error> {
+            $(let $param = $param::from_context(ctx).await?;)*
+            Ok(($($param,)*))
+        }
+    }
+});
+
+ary_context! { A }
+ary_context! { A B }
+ary_context! { A B C }
+ary_context! { A B C D }
+ary_context! { A B C D E }
+ary_context! { A B C D E F }
+ary_context! { A B C D E F G }
+ary_context! { A B C D E F G H }
+ary_context! { A B C D E F G H I }
+ary_context! { A B C D E F G H I J }
+ary_context! { A B C D E F G H I J K }
+ary_context! { A B C D E F G H I J K L }
+
 /// Generates a [`AsyncFn`] trait impl for N-ary functions where N is specified with a
 /// space separated type parameters.
 ///
@@ -14,6 +167,7 @@ pub trait AsyncFn<Args, Output> {
 /// ary! {}        // implements Handler for types: fn() -> R
 /// ary! { A B C } // implements Handler for types: fn(A, B, C) -> R
 /// ```
+#[cfg(feature = "async-trait")]
 macro_rules! ary ({ $($param:ident)* } => {
     #[async_trait::async_trait]
     impl<Func, Fut, $($param:Send + 'static,)*> AsyncFn<($($param,)*), Fut::Output> for Func
@@ -29,6 +183,25 @@ macro_rules! ary ({ $($param:ident)* } => {
     }
 });
 
+/// Same as above, but for the default allocation-free [`AsyncFn`] (`type Fut = Fut`
+/// instead of boxing the call in an `async fn`).
+#[cfg(not(feature = "async-trait"))]
+macro_rules! ary ({ $($param:ident)* } => {
+    impl<Func, Fut, $($param:Send + 'static,)*> AsyncFn<($($param,)*), Fut::Output> for Func
+    where
+        Func: Send + Sync + Fn($($param),*) -> Fut,
+        Fut: Future + Send
+    {
+        type Fut = Fut;
+
+        #[inline]
+        #[allow(non_snake_case)]
+        fn call(&self, ($($param,)*): ($($param,)*)) -> Self::Fut {
+            (self)($($param,)*)
+        }
+    }
+});
+
 ary! {}
 ary! { A }
 ary! { A B }
@@ -43,6 +216,110 @@ ary! { A B C D E F G H I J }
 ary! { A B C D E F G H I J K }
 ary! { A B C D E F G H I J K L }
 
+/// Mirrors [`AsyncFn`] for handlers that need `&mut self` access, e.g. closures that
+/// mutate captured connection state. Always boxes its future via `async-trait`, unlike
+/// the default (non-`async-trait`) [`AsyncFn`], which doesn't; stateful handlers are
+/// expected to be the exception rather than the hot path.
+#[async_trait]
+pub trait AsyncFnMut<Args, Output> {
+    async fn call_mut(&mut self, args: Args) -> Output;
+}
+
+/// Mirrors [`AsyncFn`] for handlers that consume `self`, e.g. closures that take
+/// ownership of a captured value. Always boxes its future via `async-trait`, same
+/// caveat as [`AsyncFnMut`] above.
+#[async_trait]
+pub trait AsyncFnOnce<Args, Output> {
+    async fn call_once(self, args: Args) -> Output;
+}
+
+/// Generates the [`AsyncFnMut`] and [`AsyncFnOnce`] impls for an N-ary function, where N
+/// is specified with a space separated list of type parameters. Mirrors the `ary!`
+/// macro above, but bound on `FnMut`/`FnOnce` instead of `Fn`.
+macro_rules! ary_mut_once ({ $($param:ident)* } => {
+    #[async_trait::async_trait]
+    impl<Func, Fut, $($param:Send + 'static,)*> AsyncFnMut<($($param,)*), Fut::Output> for Func
+    where
+        Func: Send + FnMut($($param),*) -> Fut,
+        Fut: Future + Send
+    {
+        #[inline]
+        #[allow(non_snake_case)]
+        async fn call_mut(&mut self, ($($param,)*): ($($param,)*)) -> Fut::Output {
+            (self)($($param,)*).await
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<Func, Fut, $($param:Send + 'static,)*> AsyncFnOnce<($($param,)*), Fut::Output> for Func
+    where
+        Func: Send + FnOnce($($param),*) -> Fut,
+        Fut: Future + Send
+    {
+        #[inline]
+        #[allow(non_snake_case)]
+        async fn call_once(self, ($($param,)*): ($($param,)*)) -> Fut::Output {
+            (self)($($param,)*).await
+        }
+    }
+});
+
+ary_mut_once! {}
+ary_mut_once! { A }
+ary_mut_once! { A B }
+ary_mut_once! { A B C }
+ary_mut_once! { A B C D }
+ary_mut_once! { A B C D E }
+ary_mut_once! { A B C D E F }
+ary_mut_once! { A B C D E F G }
+ary_mut_once! { A B C D E F G H }
+ary_mut_once! { A B C D E F G H I }
+ary_mut_once! { A B C D E F G H I J }
+ary_mut_once! { A B C D E F G H I J K }
+ary_mut_once! { A B C D E F G H I J K L }
+
+/// Flattens a (possibly nested) argument tuple into the flat arity the `ary!` impls
+/// expect, so larger argument sets can be assembled from reusable sub-extractors
+/// instead of one monolithic conversion.
+pub trait IntoArgs<Args> {
+    fn into_args(self) -> Args;
+}
+
+/// Converts a single extractable value into the 1-tuple [`AsyncFn`] expects, mirroring
+/// the `From<Request> for (Request,)` conversion in the tests below.
+impl<T> IntoArgs<(T,)> for T {
+    #[inline]
+    fn into_args(self) -> (T,) {
+        (self,)
+    }
+}
+
+/// Generates an [`IntoArgs`] impl that merges a leading value with a trailing tuple of
+/// arguments into one flat tuple, e.g. `(A, (B, C))` flattens into the 3-ary
+/// `(A, B, C)`.
+macro_rules! flatten ({ $($param:ident)* } => {
+    impl<A, $($param,)*> IntoArgs<(A, $($param,)*)> for (A, ($($param,)*)) {
+        #[inline]
+        #[allow(non_snake_case)]
+        fn into_args(self) -> (A, $($param,)*) {
+            let (a, ($($param,)*)) = self;
+            (a, $($param,)*)
+        }
+    }
+});
+
+flatten! { B }
+flatten! { B C }
+flatten! { B C D }
+flatten! { B C D E }
+flatten! { B C D E F }
+flatten! { B C D E F G }
+flatten! { B C D E F G H }
+flatten! { B C D E F G H I }
+flatten! { B C D E F G H I J }
+flatten! { B C D E F G H I J K }
+flatten! { B C D E F G H I J K L }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +347,46 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl FromContext<Request> for Body {
+        type Error = Infallible;
+
+        async fn from_context(_ctx: &Request) -> Result<Self, Self::Error> {
+            Ok(Body {})
+        }
+    }
+
+    struct Auth {}
+
+    #[derive(Debug)]
+    struct AuthError;
+
+    #[async_trait]
+    impl FromContext<Request> for Auth {
+        type Error = AuthError;
+
+        async fn from_context(_ctx: &Request) -> Result<Self, Self::Error> {
+            Ok(Auth {})
+        }
+    }
+
+    #[derive(Debug)]
+    enum ExtractError {
+        Auth(AuthError),
+    }
+
+    impl From<Infallible> for ExtractError {
+        fn from(e: Infallible) -> Self {
+            match e {}
+        }
+    }
+
+    impl From<AuthError> for ExtractError {
+        fn from(e: AuthError) -> Self {
+            ExtractError::Auth(e)
+        }
+    }
+
     fn assert_impl_fn<T, O>(_: impl AsyncFn<T, O>) {}
 
     fn assert_impl_output<T, O: Into<Response>>(_: impl AsyncFn<T, O>)
@@ -128,4 +445,98 @@ mod tests {
         assert_impl_output(with_request_resp);
         assert_impl_output(with_body_resp);
     }
+
+    fn assert_impl_extract<T, O>(_: impl AsyncFn<T, O>)
+    where
+        T: FromContext<Request>,
+    {
+    }
+
+    #[test]
+    fn test_from_context() {
+        async fn with_body(body: Body) -> &'static str {
+            let _ = body;
+            "hello"
+        }
+
+        assert_impl_extract(with_body);
+    }
+
+    fn assert_impl_multi_extract<T, O>(_: impl AsyncFn<T, O>)
+    where
+        T: FromContext<Request, Error = ExtractError>,
+    {
+    }
+
+    #[test]
+    fn test_from_context_mixed_errors() {
+        async fn with_body_and_auth(body: Body, auth: Auth) -> &'static str {
+            let _ = (body, auth);
+            "hello"
+        }
+
+        assert_impl_multi_extract(with_body_and_auth);
+    }
+
+    fn assert_impl_into_response<T, O>(_: impl AsyncFn<T, O>)
+    where
+        O: IntoResponse<Response>,
+    {
+    }
+
+    #[test]
+    fn test_call_into() {
+        async fn handler() -> &'static str {
+            "hello"
+        }
+
+        assert_impl_into_response(handler);
+    }
+
+    fn assert_impl_fn_mut<T, O>(_: impl AsyncFnMut<T, O>) {}
+
+    fn assert_impl_fn_once<T, O>(_: impl AsyncFnOnce<T, O>) {}
+
+    #[test]
+    fn test_mut_and_once_args() {
+        let mut count = 0;
+        let incr = move |n: i32| {
+            count += n;
+            async move { count }
+        };
+
+        let consume = move |n: i32| {
+            let body = Body {};
+            async move {
+                let _ = body;
+                n
+            }
+        };
+
+        assert_impl_fn_mut(incr);
+        assert_impl_fn_once(consume);
+    }
+
+    #[test]
+    fn test_mut_with_non_sync_capture() {
+        use std::cell::Cell;
+
+        let state = Cell::new(0);
+        let incr = move |n: i32| {
+            state.set(state.get() + n);
+            let total = state.get();
+            async move { total }
+        };
+
+        assert_impl_fn_mut(incr);
+    }
+
+    #[test]
+    fn test_flatten_args() {
+        let req = Request {};
+        let nested = (req, (Body {}, 8u8));
+
+        let (req, body, n) = nested.into_args();
+        let _ = (req, body, n);
+    }
 }